@@ -0,0 +1,319 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::{Service, ServiceFactory};
+
+/// Service for the `and_then` combinator, chaining a computation onto the end
+/// of another service.
+///
+/// This is created by the `ServiceExt::and_then` method.
+pub struct AndThen<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> AndThen<A, B> {
+    /// Create new `AndThen` combinator
+    pub(crate) fn new<Req>(a: A, b: B) -> Self
+    where
+        A: Service<Req>,
+        B: Service<A::Response, Error = A::Error>,
+    {
+        Self { a, b }
+    }
+}
+
+impl<A, B> Clone for AndThen<A, B>
+where
+    A: Clone,
+    B: Clone,
+{
+    fn clone(&self) -> Self {
+        AndThen {
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl<A, B, Req> Service<Req> for AndThen<A, B>
+where
+    A: Service<Req>,
+    B: Service<A::Response, Error = A::Error>,
+{
+    type Response = B::Response;
+    type Error = A::Error;
+    type Future = AndThenFuture<A, B, Req>;
+
+    fn poll_ready(&mut self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let not_ready = self.a.poll_ready(ctx)?.is_pending();
+        if self.b.poll_ready(ctx)?.is_pending() || not_ready {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        AndThenFuture::new(self.a.call(req), &mut self.b)
+    }
+}
+
+#[pin_project::pin_project(project = AndThenFutureProj)]
+pub enum AndThenFuture<A, B, Req>
+where
+    A: Service<Req>,
+    B: Service<A::Response, Error = A::Error>,
+{
+    A {
+        #[pin]
+        fut: A::Future,
+        b: *mut B,
+    },
+    B {
+        #[pin]
+        fut: B::Future,
+    },
+    Empty,
+}
+
+impl<A, B, Req> AndThenFuture<A, B, Req>
+where
+    A: Service<Req>,
+    B: Service<A::Response, Error = A::Error>,
+{
+    fn new(fut: A::Future, b: &mut B) -> Self {
+        AndThenFuture::A { fut, b }
+    }
+}
+
+impl<A, B, Req> Future for AndThenFuture<A, B, Req>
+where
+    A: Service<Req>,
+    B: Service<A::Response, Error = A::Error>,
+{
+    type Output = Result<B::Response, A::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match self.as_mut().project() {
+                AndThenFutureProj::A { fut, b } => match fut.poll(cx)? {
+                    Poll::Ready(resp) => {
+                        // SAFETY: `b` is a valid pointer to the `B` service owned by the
+                        // `AndThen` that produced this future, which outlives the future.
+                        let b = unsafe { &mut **b };
+                        let fut = b.call(resp);
+                        self.as_mut().set(AndThenFuture::B { fut });
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                AndThenFutureProj::B { fut } => {
+                    let res = futures_util::ready!(fut.poll(cx));
+                    self.set(AndThenFuture::Empty);
+                    return Poll::Ready(res);
+                }
+                AndThenFutureProj::Empty => unreachable!("poll called after future completed"),
+            }
+        }
+    }
+}
+
+/// Factory for the `and_then` combinator, chaining a computation onto the end
+/// of another service factory.
+///
+/// This is created by the `ServiceFactoryExt::and_then` method.
+pub struct AndThenServiceFactory<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> AndThenServiceFactory<A, B> {
+    /// Create new `AndThenServiceFactory` combinator
+    pub(crate) fn new<Req>(a: A, b: B) -> Self
+    where
+        A: ServiceFactory<Req>,
+        A::Config: Clone,
+        B: ServiceFactory<
+            A::Response,
+            Config = A::Config,
+            Error = A::Error,
+            InitError = A::InitError,
+        >,
+    {
+        Self { a, b }
+    }
+}
+
+impl<A, B> Clone for AndThenServiceFactory<A, B>
+where
+    A: Clone,
+    B: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl<A, B, Req> ServiceFactory<Req> for AndThenServiceFactory<A, B>
+where
+    A: ServiceFactory<Req>,
+    A::Config: Clone,
+    B: ServiceFactory<
+        A::Response,
+        Config = A::Config,
+        Error = A::Error,
+        InitError = A::InitError,
+    >,
+{
+    type Response = B::Response;
+    type Error = A::Error;
+    type Config = A::Config;
+    type Service = AndThen<A::Service, B::Service>;
+    type InitError = A::InitError;
+    type Future = AndThenServiceFactoryFuture<A, B, Req>;
+
+    fn new_service(&self, cfg: A::Config) -> Self::Future {
+        AndThenServiceFactoryFuture {
+            fut_a: self.a.new_service(cfg.clone()),
+            fut_b: self.b.new_service(cfg),
+            a: None,
+            b: None,
+        }
+    }
+}
+
+#[pin_project::pin_project]
+pub struct AndThenServiceFactoryFuture<A, B, Req>
+where
+    A: ServiceFactory<Req>,
+    B: ServiceFactory<
+        A::Response,
+        Config = A::Config,
+        Error = A::Error,
+        InitError = A::InitError,
+    >,
+{
+    #[pin]
+    fut_a: A::Future,
+    #[pin]
+    fut_b: B::Future,
+    a: Option<A::Service>,
+    b: Option<B::Service>,
+}
+
+impl<A, B, Req> Future for AndThenServiceFactoryFuture<A, B, Req>
+where
+    A: ServiceFactory<Req>,
+    B: ServiceFactory<
+        A::Response,
+        Config = A::Config,
+        Error = A::Error,
+        InitError = A::InitError,
+    >,
+{
+    type Output = Result<AndThen<A::Service, B::Service>, A::InitError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.a.is_none() {
+            if let Poll::Ready(service) = this.fut_a.poll(cx)? {
+                *this.a = Some(service);
+            }
+        }
+        if this.b.is_none() {
+            if let Poll::Ready(service) = this.fut_b.poll(cx)? {
+                *this.b = Some(service);
+            }
+        }
+        if this.a.is_some() && this.b.is_some() {
+            Poll::Ready(Ok(AndThen::new(
+                this.a.take().unwrap(),
+                this.b.take().unwrap(),
+            )))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use futures_util::future::{lazy, ok, Ready};
+
+    use super::*;
+    use crate::{IntoServiceFactory, Service, ServiceExt, ServiceFactory, ServiceFactoryExt};
+
+    #[derive(Clone)]
+    struct Srv1(Rc<Cell<usize>>);
+
+    impl Service<&'static str> for Srv1 {
+        type Response = &'static str;
+        type Error = ();
+        type Future = Ready<Result<Self::Response, ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.0.set(self.0.get() + 1);
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: &'static str) -> Self::Future {
+            ok(req)
+        }
+    }
+
+    struct Srv2(Rc<Cell<usize>>);
+
+    impl Service<&'static str> for Srv2 {
+        type Response = (&'static str, &'static str);
+        type Error = ();
+        type Future = Ready<Result<Self::Response, ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.0.set(self.0.get() + 1);
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: &'static str) -> Self::Future {
+            ok((req, "srv2"))
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_poll_ready() {
+        let cnt = Rc::new(Cell::new(0));
+        let mut srv = Srv1(cnt.clone()).and_then(Srv2(cnt.clone()));
+        let res = lazy(|cx| srv.poll_ready(cx)).await;
+        assert_eq!(res, Poll::Ready(Ok(())));
+        assert_eq!(cnt.get(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn test_call() {
+        let cnt = Rc::new(Cell::new(0));
+        let mut srv = Srv1(cnt.clone()).and_then(Srv2(cnt));
+        let res = srv.call("srv1").await;
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), ("srv1", "srv2"));
+    }
+
+    #[actix_rt::test]
+    async fn test_new_service() {
+        let cnt = Rc::new(Cell::new(0));
+        let cnt2 = cnt.clone();
+        let new_srv = (move |_: &()| ok::<_, ()>(Srv1(cnt2.clone())))
+            .into_factory()
+            .and_then(move |_: &()| ok::<_, ()>(Srv2(cnt.clone())));
+        let mut srv = new_srv.new_service(&()).await.unwrap();
+        let res = srv.call("srv1").await;
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), ("srv1", "srv2"));
+    }
+}
@@ -0,0 +1,205 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use super::{Service, ServiceFactory};
+
+/// Create a `ServiceFactory` whose `new_service(cfg)` calls `f(cfg, &mut service)`
+/// and awaits the produced service.
+///
+/// This allows deferring part of a service's construction until a
+/// per-connection/per-worker `Config` is available, which is something the
+/// `Map`-style factories cannot express since they only see the inner
+/// service's already-resolved response type.
+pub fn apply_cfg<F, Cfg, S1, Req, S2, Fut, Err>(service: S1, f: F) -> ApplyConfig<F, Cfg, S1, Req, S2, Fut, Err>
+where
+    S1: Service<Req>,
+    F: FnMut(Cfg, &mut S1) -> Fut,
+    Fut: Future<Output = Result<S2, Err>>,
+    S2: Service<Req>,
+{
+    ApplyConfig {
+        srv: Rc::new(RefCell::new((service, f))),
+        _t: PhantomData,
+    }
+}
+
+/// Create a `ServiceFactory` whose `new_service(cfg)` first builds `S1` from
+/// `factory` (with `Config = ()`), then calls `f(cfg, &mut service)` and
+/// awaits the produced service.
+pub fn apply_cfg_factory<F, Cfg, T, Req, S2, Fut, Err>(
+    factory: T,
+    f: F,
+) -> ApplyConfigFactory<F, Cfg, T, Req, S2, Fut, Err>
+where
+    T: ServiceFactory<Req, Config = ()>,
+    T::Future: 'static,
+    F: FnMut(Cfg, &mut T::Service) -> Fut,
+    F: 'static,
+    Cfg: 'static,
+    Fut: Future<Output = Result<S2, Err>>,
+    S2: Service<Req>,
+    Err: From<T::InitError>,
+{
+    ApplyConfigFactory {
+        factory,
+        f: Rc::new(RefCell::new(f)),
+        _t: PhantomData,
+    }
+}
+
+/// `ServiceFactory` returned by `apply_cfg`.
+pub struct ApplyConfig<F, Cfg, S1, Req, S2, Fut, Err>
+where
+    S1: Service<Req>,
+    F: FnMut(Cfg, &mut S1) -> Fut,
+    Fut: Future<Output = Result<S2, Err>>,
+    S2: Service<Req>,
+{
+    srv: Rc<RefCell<(S1, F)>>,
+    _t: PhantomData<(Cfg, Req, S2, Fut, Err)>,
+}
+
+impl<F, Cfg, S1, Req, S2, Fut, Err> Clone for ApplyConfig<F, Cfg, S1, Req, S2, Fut, Err>
+where
+    S1: Service<Req>,
+    F: FnMut(Cfg, &mut S1) -> Fut,
+    Fut: Future<Output = Result<S2, Err>>,
+    S2: Service<Req>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            srv: self.srv.clone(),
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<F, Cfg, S1, Req, S2, Fut, Err> ServiceFactory<Req> for ApplyConfig<F, Cfg, S1, Req, S2, Fut, Err>
+where
+    S1: Service<Req>,
+    F: FnMut(Cfg, &mut S1) -> Fut,
+    Fut: Future<Output = Result<S2, Err>>,
+    S2: Service<Req>,
+{
+    type Response = S2::Response;
+    type Error = S2::Error;
+    type Config = Cfg;
+    type Service = S2;
+    type InitError = Err;
+    type Future = Fut;
+
+    fn new_service(&self, cfg: Cfg) -> Self::Future {
+        let mut borrowed = self.srv.borrow_mut();
+        let (srv, f) = &mut *borrowed;
+        f(cfg, srv)
+    }
+}
+
+/// `ServiceFactory` returned by `apply_cfg_factory`.
+pub struct ApplyConfigFactory<F, Cfg, T, Req, S2, Fut, Err>
+where
+    T: ServiceFactory<Req, Config = ()>,
+    F: FnMut(Cfg, &mut T::Service) -> Fut,
+    Fut: Future<Output = Result<S2, Err>>,
+    S2: Service<Req>,
+    Err: From<T::InitError>,
+{
+    factory: T,
+    f: Rc<RefCell<F>>,
+    _t: PhantomData<(Cfg, Req, S2, Fut, Err)>,
+}
+
+impl<F, Cfg, T, Req, S2, Fut, Err> Clone for ApplyConfigFactory<F, Cfg, T, Req, S2, Fut, Err>
+where
+    T: ServiceFactory<Req, Config = ()> + Clone,
+    F: FnMut(Cfg, &mut T::Service) -> Fut,
+    Fut: Future<Output = Result<S2, Err>>,
+    S2: Service<Req>,
+    Err: From<T::InitError>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            factory: self.factory.clone(),
+            f: self.f.clone(),
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<F, Cfg, T, Req, S2, Fut, Err> ServiceFactory<Req> for ApplyConfigFactory<F, Cfg, T, Req, S2, Fut, Err>
+where
+    T: ServiceFactory<Req, Config = ()>,
+    T::Future: 'static,
+    F: FnMut(Cfg, &mut T::Service) -> Fut,
+    F: 'static,
+    Cfg: 'static,
+    Fut: Future<Output = Result<S2, Err>>,
+    S2: Service<Req>,
+    Err: From<T::InitError>,
+{
+    type Response = S2::Response;
+    type Error = S2::Error;
+    type Config = Cfg;
+    type Service = S2;
+    type InitError = Err;
+    type Future = Pin<Box<dyn Future<Output = Result<S2, Err>>>>;
+
+    fn new_service(&self, cfg: Cfg) -> Self::Future {
+        let fut = self.factory.new_service(());
+        let f = self.f.clone();
+        Box::pin(async move {
+            let mut srv = fut.await.map_err(Err::from)?;
+            let call_fut = {
+                let mut borrowed = f.borrow_mut();
+                (*borrowed)(cfg, &mut srv)
+            };
+            call_fut.await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::{Context, Poll};
+
+    use futures_util::future::{ok, Ready};
+
+    use super::*;
+    use crate::{IntoServiceFactory, Service, ServiceFactory};
+
+    struct Srv;
+
+    impl Service<()> for Srv {
+        type Response = ();
+        type Error = ();
+        type Future = Ready<Result<(), ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: ()) -> Self::Future {
+            ok(())
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_apply_cfg() {
+        let factory = apply_cfg(Srv, |_cfg: usize, _srv: &mut Srv| ok::<_, ()>(Srv));
+        let mut srv = factory.new_service(1).await.unwrap();
+        assert!(srv.call(()).await.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_apply_cfg_factory() {
+        let factory = apply_cfg_factory(
+            (|_: ()| ok::<_, ()>(Srv)).into_factory(),
+            |_cfg: usize, _srv: &mut Srv| ok::<_, ()>(Srv),
+        );
+        let mut srv = factory.new_service(1).await.unwrap();
+        assert!(srv.call(()).await.is_ok());
+    }
+}
@@ -0,0 +1,178 @@
+//! Type-erased `Service` and `ServiceFactory` wrappers.
+//!
+//! Combinator chains like `Map`/`AndThen` produce deeply nested, unnameable
+//! types. `BoxService` and `BoxServiceFactory` erase those into a single
+//! concrete type that can be named in structs and function signatures.
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::{Service, ServiceFactory};
+
+/// A boxed future, as returned by `BoxService`.
+pub type BoxFuture<Res, Err> = Pin<Box<dyn Future<Output = Result<Res, Err>>>>;
+
+/// Create a type-erased `Service` wrapping `service`.
+pub fn service<S, Req>(service: S) -> BoxService<Req, S::Response, S::Error>
+where
+    S: Service<Req> + 'static,
+    Req: 'static,
+    S::Future: 'static,
+{
+    BoxService(Box::new(ServiceWrapper(service)))
+}
+
+/// Create a type-erased `ServiceFactory` wrapping `factory`.
+pub fn factory<T, Req>(
+    factory: T,
+) -> BoxServiceFactory<T::Config, Req, T::Response, T::Error, T::InitError>
+where
+    T: ServiceFactory<Req> + 'static,
+    Req: 'static,
+    T::Future: 'static,
+    T::Service: 'static,
+    <T::Service as Service<Req>>::Future: 'static,
+{
+    BoxServiceFactory(Box::new(FactoryWrapper(factory)))
+}
+
+/// A type-erased `Service`.
+///
+/// Since `Service::call` takes `&mut self`, the underlying trait object does
+/// not need to be `Sync`; see [`UnsyncBoxService`] for an alias making this
+/// explicit.
+pub struct BoxService<Req, Res, Err>(
+    Box<dyn Service<Req, Response = Res, Error = Err, Future = BoxFuture<Res, Err>>>,
+);
+
+/// An alias for [`BoxService`], spelled out to make clear the erased service
+/// is not required to be `Sync`.
+pub type UnsyncBoxService<Req, Res, Err> = BoxService<Req, Res, Err>;
+
+impl<Req, Res, Err> Service<Req> for BoxService<Req, Res, Err> {
+    type Response = Res;
+    type Error = Err;
+    type Future = BoxFuture<Res, Err>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        self.0.call(req)
+    }
+}
+
+struct ServiceWrapper<S>(S);
+
+impl<S, Req> Service<Req> for ServiceWrapper<S>
+where
+    S: Service<Req>,
+    S::Future: 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<S::Response, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        Box::pin(self.0.call(req))
+    }
+}
+
+/// A type-erased `ServiceFactory`.
+pub struct BoxServiceFactory<Cfg, Req, Res, Err, InitErr>(
+    Box<
+        dyn ServiceFactory<
+            Req,
+            Config = Cfg,
+            Response = Res,
+            Error = Err,
+            InitError = InitErr,
+            Service = BoxService<Req, Res, Err>,
+            Future = BoxFuture<BoxService<Req, Res, Err>, InitErr>,
+        >,
+    >,
+);
+
+impl<Cfg, Req, Res, Err, InitErr> ServiceFactory<Req>
+    for BoxServiceFactory<Cfg, Req, Res, Err, InitErr>
+{
+    type Response = Res;
+    type Error = Err;
+    type Config = Cfg;
+    type Service = BoxService<Req, Res, Err>;
+    type InitError = InitErr;
+    type Future = BoxFuture<Self::Service, InitErr>;
+
+    fn new_service(&self, cfg: Cfg) -> Self::Future {
+        self.0.new_service(cfg)
+    }
+}
+
+struct FactoryWrapper<T>(T);
+
+impl<T, Req> ServiceFactory<Req> for FactoryWrapper<T>
+where
+    T: ServiceFactory<Req>,
+    Req: 'static,
+    T::Future: 'static,
+    T::Service: 'static,
+    <T::Service as Service<Req>>::Future: 'static,
+{
+    type Response = T::Response;
+    type Error = T::Error;
+    type Config = T::Config;
+    type Service = BoxService<Req, T::Response, T::Error>;
+    type InitError = T::InitError;
+    type Future = BoxFuture<Self::Service, T::InitError>;
+
+    fn new_service(&self, cfg: T::Config) -> Self::Future {
+        let fut = self.0.new_service(cfg);
+        Box::pin(async move { Ok(service(fut.await?)) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use futures_util::future::{lazy, ok, Ready};
+
+    use super::*;
+    use crate::{IntoServiceFactory, Service, ServiceFactory};
+
+    struct Srv;
+
+    impl Service<()> for Srv {
+        type Response = ();
+        type Error = ();
+        type Future = Ready<Result<(), ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: ()) -> Self::Future {
+            ok(())
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_boxed_service() {
+        let mut srv = service(Srv);
+        let res = lazy(|cx| srv.poll_ready(cx)).await;
+        assert_eq!(res, Poll::Ready(Ok(())));
+        assert!(srv.call(()).await.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_boxed_factory() {
+        let new_srv = factory((|_: &()| ok::<_, ()>(Srv)).into_factory());
+        let mut srv = new_srv.new_service(&()).await.unwrap();
+        assert!(srv.call(()).await.is_ok());
+    }
+}
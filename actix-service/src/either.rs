@@ -0,0 +1,194 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::{Service, ServiceFactory};
+
+/// Combine two different service types into a single type.
+///
+/// Useful for cases where a service must be chosen at runtime between two
+/// pipelines that share the same `Response` and `Error` types but would
+/// otherwise have different, unnameable concrete types.
+pub enum Either<A, B> {
+    A(A),
+    B(B),
+}
+
+impl<A, B, Req> Service<Req> for Either<A, B>
+where
+    A: Service<Req>,
+    B: Service<Req, Response = A::Response, Error = A::Error>,
+{
+    type Response = A::Response;
+    type Error = A::Error;
+    type Future = EitherFuture<A::Future, B::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self {
+            Either::A(service) => service.poll_ready(cx),
+            Either::B(service) => service.poll_ready(cx),
+        }
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        match self {
+            Either::A(service) => EitherFuture::A { fut: service.call(req) },
+            Either::B(service) => EitherFuture::B { fut: service.call(req) },
+        }
+    }
+}
+
+#[pin_project::pin_project(project = EitherFutureProj)]
+pub enum EitherFuture<A, B> {
+    A {
+        #[pin]
+        fut: A,
+    },
+    B {
+        #[pin]
+        fut: B,
+    },
+}
+
+impl<A, B, Res, Err> Future for EitherFuture<A, B>
+where
+    A: Future<Output = Result<Res, Err>>,
+    B: Future<Output = Result<Res, Err>>,
+{
+    type Output = Result<Res, Err>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            EitherFutureProj::A { fut } => fut.poll(cx),
+            EitherFutureProj::B { fut } => fut.poll(cx),
+        }
+    }
+}
+
+/// Combine two different service factory types into a single type.
+///
+/// Builds whichever `Either` variant was selected when the factory was
+/// created.
+pub enum EitherServiceFactory<A, B> {
+    A(A),
+    B(B),
+}
+
+impl<A, B, Req> ServiceFactory<Req> for EitherServiceFactory<A, B>
+where
+    A: ServiceFactory<Req>,
+    B: ServiceFactory<
+        Req,
+        Config = A::Config,
+        Response = A::Response,
+        Error = A::Error,
+        InitError = A::InitError,
+    >,
+{
+    type Response = A::Response;
+    type Error = A::Error;
+    type Config = A::Config;
+    type Service = Either<A::Service, B::Service>;
+    type InitError = A::InitError;
+    type Future = EitherFactoryFuture<A::Future, B::Future>;
+
+    fn new_service(&self, cfg: A::Config) -> Self::Future {
+        match self {
+            EitherServiceFactory::A(factory) => EitherFactoryFuture::A {
+                fut: factory.new_service(cfg),
+            },
+            EitherServiceFactory::B(factory) => EitherFactoryFuture::B {
+                fut: factory.new_service(cfg),
+            },
+        }
+    }
+}
+
+#[pin_project::pin_project(project = EitherFactoryFutureProj)]
+pub enum EitherFactoryFuture<A, B> {
+    A {
+        #[pin]
+        fut: A,
+    },
+    B {
+        #[pin]
+        fut: B,
+    },
+}
+
+impl<A, B, SA, SB, Err> Future for EitherFactoryFuture<A, B>
+where
+    A: Future<Output = Result<SA, Err>>,
+    B: Future<Output = Result<SB, Err>>,
+{
+    type Output = Result<Either<SA, SB>, Err>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            EitherFactoryFutureProj::A { fut } => match fut.poll(cx) {
+                Poll::Ready(res) => Poll::Ready(res.map(Either::A)),
+                Poll::Pending => Poll::Pending,
+            },
+            EitherFactoryFutureProj::B { fut } => match fut.poll(cx) {
+                Poll::Ready(res) => Poll::Ready(res.map(Either::B)),
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use futures_util::future::{ok, Ready};
+
+    use super::*;
+    use crate::ServiceExt;
+
+    struct Srv1;
+
+    impl Service<()> for Srv1 {
+        type Response = &'static str;
+        type Error = ();
+        type Future = Ready<Result<Self::Response, ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: ()) -> Self::Future {
+            ok("srv1")
+        }
+    }
+
+    struct Srv2;
+
+    impl Service<()> for Srv2 {
+        type Response = &'static str;
+        type Error = ();
+        type Future = Ready<Result<Self::Response, ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: ()) -> Self::Future {
+            ok("srv2")
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_either_a() {
+        let srv: Either<Srv1, Srv2> = Either::A(Srv1);
+        let res = srv.oneshot(()).await;
+        assert_eq!(res.unwrap(), "srv1");
+    }
+
+    #[actix_rt::test]
+    async fn test_either_b() {
+        let srv: Either<Srv1, Srv2> = Either::B(Srv2);
+        let res = srv.oneshot(()).await;
+        assert_eq!(res.unwrap(), "srv2");
+    }
+}
@@ -0,0 +1,193 @@
+#![deny(rust_2018_idioms, warnings)]
+#![allow(clippy::type_complexity)]
+
+//! See [`Service`] and [`ServiceFactory`] for the foundational traits this crate is built around.
+
+mod and_then;
+mod apply;
+pub mod boxed;
+mod either;
+mod map;
+mod oneshot;
+mod stream;
+
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use futures_util::stream::Stream;
+
+pub use self::and_then::{AndThen, AndThenServiceFactory};
+pub use self::apply::{apply_cfg, apply_cfg_factory, ApplyConfig, ApplyConfigFactory};
+pub use self::either::{Either, EitherServiceFactory};
+pub use self::map::{Map, MapServiceFactory};
+pub use self::oneshot::Oneshot;
+pub use self::stream::{CallAll, CallAllUnordered};
+
+/// An asynchronous operation from `Request` to a `Result<Response, Error>`.
+///
+/// The `Service` trait is a simplified interface making it easy to write
+/// network application as a set of decoupled components that can be combined
+/// into one application.
+pub trait Service<Req> {
+    /// Responses given by the service.
+    type Response;
+
+    /// Errors produced by the service.
+    type Error;
+
+    /// The future response value.
+    type Future: Future<Output = Result<Self::Response, Self::Error>>;
+
+    /// Returns `Ready` when the service is able to process requests.
+    fn poll_ready(&mut self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Process the request and return the response asynchronously.
+    fn call(&mut self, req: Req) -> Self::Future;
+}
+
+/// Factory for creating `Service`s.
+///
+/// This is useful for cases where new `Service`s must be produced for each
+/// request, such as to produce per-connection state.
+pub trait ServiceFactory<Req> {
+    /// Responses given by the created services.
+    type Response;
+
+    /// Errors produced by the created services.
+    type Error;
+
+    /// Service factory configuration.
+    type Config;
+
+    /// The kind of `Service` created by this factory.
+    type Service: Service<Req, Response = Self::Response, Error = Self::Error>;
+
+    /// Errors potentially raised while building a service.
+    type InitError;
+
+    /// The future of the `Service` instance.
+    type Future: Future<Output = Result<Self::Service, Self::InitError>>;
+
+    /// Create and return a new service asynchronously.
+    fn new_service(&self, cfg: Self::Config) -> Self::Future;
+}
+
+/// Trait for converting a value into a `ServiceFactory`.
+pub trait IntoServiceFactory<T, Req>
+where
+    T: ServiceFactory<Req>,
+{
+    /// Convert `Self` into a `ServiceFactory`.
+    fn into_factory(self) -> T;
+}
+
+impl<T, Req> IntoServiceFactory<T, Req> for T
+where
+    T: ServiceFactory<Req>,
+{
+    fn into_factory(self) -> T {
+        self
+    }
+}
+
+/// Convert an `Fn(Cfg) -> Fut` closure into a `ServiceFactory`.
+impl<F, Cfg, Fut, Req, Svc, Err> ServiceFactory<Req> for F
+where
+    F: Fn(Cfg) -> Fut,
+    Fut: Future<Output = Result<Svc, Err>>,
+    Svc: Service<Req>,
+{
+    type Response = Svc::Response;
+    type Error = Svc::Error;
+    type Config = Cfg;
+    type Service = Svc;
+    type InitError = Err;
+    type Future = Fut;
+
+    fn new_service(&self, cfg: Cfg) -> Self::Future {
+        (self)(cfg)
+    }
+}
+
+/// An extension trait adding combinators on top of the `Service` trait.
+pub trait ServiceExt<Req>: Service<Req> {
+    /// Map this service's output to a different type, returning a new service
+    /// of the resulting type.
+    fn map<F, R>(self, f: F) -> Map<Self, F, Req, R>
+    where
+        Self: Sized,
+        F: FnMut(Self::Response) -> R,
+    {
+        Map::new(self, f)
+    }
+
+    /// Call another service after completion of this one.
+    fn and_then<B>(self, service: B) -> AndThen<Self, B>
+    where
+        Self: Sized,
+        B: Service<Self::Response, Error = Self::Error>,
+    {
+        AndThen::new(self, service)
+    }
+
+    /// Drive this service to readiness and issue a single `call`, resolving
+    /// to the result of that call.
+    fn oneshot(self, req: Req) -> Oneshot<Self, Req>
+    where
+        Self: Sized,
+    {
+        Oneshot::new(self, req)
+    }
+
+    /// Feed a stream of requests through this service, yielding a stream of
+    /// responses in the same order the requests arrived in.
+    fn call_all<St>(self, stream: St) -> CallAll<Self, St>
+    where
+        Self: Sized,
+        St: Stream<Item = Req>,
+    {
+        CallAll::new(self, stream)
+    }
+
+    /// Feed a stream of requests through this service, yielding responses as
+    /// soon as each one completes rather than preserving request order.
+    fn call_all_unordered<St>(self, stream: St) -> CallAllUnordered<Self, St>
+    where
+        Self: Sized,
+        St: Stream<Item = Req>,
+    {
+        CallAllUnordered::new(self, stream)
+    }
+}
+
+impl<T, Req> ServiceExt<Req> for T where T: Service<Req> {}
+
+/// An extension trait adding combinators on top of the `ServiceFactory` trait.
+pub trait ServiceFactoryExt<Req>: ServiceFactory<Req> {
+    /// Map this service's output to a different type, returning a new service
+    /// of the resulting type.
+    fn map<F, R>(self, f: F) -> MapServiceFactory<Self, F, Req, R>
+    where
+        Self: Sized,
+        F: FnMut(Self::Response) -> R + Clone,
+    {
+        MapServiceFactory::new(self, f)
+    }
+
+    /// Call another service after completion of this one.
+    fn and_then<B>(self, factory: B) -> AndThenServiceFactory<Self, B>
+    where
+        Self: Sized,
+        Self::Config: Clone,
+        B: ServiceFactory<
+            Self::Response,
+            Error = Self::Error,
+            Config = Self::Config,
+            InitError = Self::InitError,
+        >,
+    {
+        AndThenServiceFactory::new(self, factory)
+    }
+}
+
+impl<T, Req> ServiceFactoryExt<Req> for T where T: ServiceFactory<Req> {}
@@ -198,7 +198,7 @@ mod tests {
     use futures_util::future::{lazy, ok, Ready};
 
     use super::*;
-    use crate::{IntoServiceFactory, Service, ServiceFactory};
+    use crate::{IntoServiceFactory, Service, ServiceExt, ServiceFactory, ServiceFactoryExt};
 
     struct Srv;
 
@@ -233,7 +233,7 @@ mod tests {
 
     #[actix_rt::test]
     async fn test_new_service() {
-        let new_srv = (|| ok::<_, ()>(Srv)).into_factory().map(|_| "ok");
+        let new_srv = (|_: &()| ok::<_, ()>(Srv)).into_factory().map(|_| "ok");
         let mut srv = new_srv.new_service(&()).await.unwrap();
         let res = srv.call(()).await;
         assert!(res.is_ok());
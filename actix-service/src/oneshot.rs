@@ -0,0 +1,97 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::Service;
+
+/// A future representing a single call to a `Service`, driving it to
+/// readiness before issuing the call.
+///
+/// This is created by the `ServiceExt::oneshot` method.
+#[pin_project::pin_project(project = OneshotProj)]
+pub enum Oneshot<S, Req>
+where
+    S: Service<Req>,
+{
+    NotReady { service: S, req: Option<Req> },
+    Called { #[pin] fut: S::Future },
+    Done,
+}
+
+impl<S, Req> Oneshot<S, Req>
+where
+    S: Service<Req>,
+{
+    pub(crate) fn new(service: S, req: Req) -> Self {
+        Oneshot::NotReady {
+            service,
+            req: Some(req),
+        }
+    }
+}
+
+impl<S, Req> Future for Oneshot<S, Req>
+where
+    S: Service<Req>,
+{
+    type Output = Result<S::Response, S::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match self.as_mut().project() {
+                OneshotProj::NotReady { service, req } => match service.poll_ready(cx)? {
+                    Poll::Ready(()) => {
+                        let req = req.take().expect("Oneshot polled after completion");
+                        let fut = service.call(req);
+                        self.set(Oneshot::Called { fut });
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                OneshotProj::Called { fut } => {
+                    let res = futures_util::ready!(fut.poll(cx));
+                    self.set(Oneshot::Done);
+                    return Poll::Ready(res);
+                }
+                OneshotProj::Done => panic!("Oneshot polled after completion"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use futures_util::future::{ok, Ready};
+
+    use super::*;
+    use crate::ServiceExt;
+
+    struct Srv(Rc<Cell<usize>>);
+
+    impl Service<&'static str> for Srv {
+        type Response = &'static str;
+        type Error = ();
+        type Future = Ready<Result<Self::Response, ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.0.set(self.0.get() + 1);
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: &'static str) -> Self::Future {
+            ok(req)
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_oneshot() {
+        let cnt = Rc::new(Cell::new(0));
+        let srv = Srv(cnt.clone());
+        let res = srv.oneshot("srv1").await;
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), "srv1");
+        assert_eq!(cnt.get(), 1);
+    }
+}
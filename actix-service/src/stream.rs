@@ -0,0 +1,193 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::{FuturesOrdered, FuturesUnordered, Stream};
+
+use super::Service;
+
+/// A `Stream` that drives requests pulled from another `Stream` through a
+/// `Service`, yielding responses in the same order the requests arrived in.
+///
+/// This is created by the `ServiceExt::call_all` method.
+///
+/// The source stream is only polled for a new request once the service
+/// reports ready, giving the combination of stream and service
+/// backpressure-aware behaviour. Once the source stream ends, any
+/// still-in-flight service calls are drained before the adapter itself ends.
+#[pin_project::pin_project]
+pub struct CallAll<S, St>
+where
+    S: Service<St::Item>,
+    St: Stream,
+{
+    service: S,
+    #[pin]
+    stream: St,
+    eof: bool,
+    #[pin]
+    queue: FuturesOrdered<S::Future>,
+}
+
+impl<S, St> CallAll<S, St>
+where
+    S: Service<St::Item>,
+    St: Stream,
+{
+    pub(crate) fn new(service: S, stream: St) -> Self {
+        Self {
+            service,
+            stream,
+            eof: false,
+            queue: FuturesOrdered::new(),
+        }
+    }
+}
+
+impl<S, St> Stream for CallAll<S, St>
+where
+    S: Service<St::Item>,
+    St: Stream,
+{
+    type Item = Result<S::Response, S::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if !*this.eof {
+            while let Poll::Ready(()) = this.service.poll_ready(cx)? {
+                match this.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(req)) => {
+                        this.queue.push_back(this.service.call(req));
+                    }
+                    Poll::Ready(None) => {
+                        *this.eof = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+        }
+
+        match this.queue.as_mut().poll_next(cx) {
+            Poll::Ready(Some(res)) => Poll::Ready(Some(res)),
+            Poll::Ready(None) if *this.eof => Poll::Ready(None),
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A `Stream` like [`CallAll`], but one that yields responses as soon as they
+/// complete rather than preserving request order.
+///
+/// This is created by the `ServiceExt::call_all_unordered` method.
+#[pin_project::pin_project]
+pub struct CallAllUnordered<S, St>
+where
+    S: Service<St::Item>,
+    St: Stream,
+{
+    service: S,
+    #[pin]
+    stream: St,
+    eof: bool,
+    #[pin]
+    queue: FuturesUnordered<S::Future>,
+}
+
+impl<S, St> CallAllUnordered<S, St>
+where
+    S: Service<St::Item>,
+    St: Stream,
+{
+    pub(crate) fn new(service: S, stream: St) -> Self {
+        Self {
+            service,
+            stream,
+            eof: false,
+            queue: FuturesUnordered::new(),
+        }
+    }
+}
+
+impl<S, St> Stream for CallAllUnordered<S, St>
+where
+    S: Service<St::Item>,
+    St: Stream,
+{
+    type Item = Result<S::Response, S::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if !*this.eof {
+            while let Poll::Ready(()) = this.service.poll_ready(cx)? {
+                match this.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(req)) => {
+                        this.queue.push(this.service.call(req));
+                    }
+                    Poll::Ready(None) => {
+                        *this.eof = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+        }
+
+        match this.queue.as_mut().poll_next(cx) {
+            Poll::Ready(Some(res)) => Poll::Ready(Some(res)),
+            Poll::Ready(None) if *this.eof => Poll::Ready(None),
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use futures_util::future::{ok, Ready};
+    use futures_util::stream::{self, StreamExt};
+
+    use super::*;
+    use crate::ServiceExt;
+
+    struct Srv;
+
+    impl Service<u32> for Srv {
+        type Response = u32;
+        type Error = ();
+        type Future = Ready<Result<u32, ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: u32) -> Self::Future {
+            ok(req * 2)
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_call_all_preserves_order() {
+        let results: Vec<_> = Srv
+            .call_all(stream::iter(vec![1, 2, 3]))
+            .collect()
+            .await;
+        let results: Result<Vec<_>, ()> = results.into_iter().collect();
+        assert_eq!(results.unwrap(), vec![2, 4, 6]);
+    }
+
+    #[actix_rt::test]
+    async fn test_call_all_unordered_yields_every_response() {
+        let mut results: Vec<_> = Srv
+            .call_all_unordered(stream::iter(vec![1, 2, 3]))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, ()>>()
+            .unwrap();
+        results.sort_unstable();
+        assert_eq!(results, vec![2, 4, 6]);
+    }
+}